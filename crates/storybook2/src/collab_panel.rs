@@ -1,11 +1,33 @@
-use crate::theme::{theme, Theme};
+mod model;
+
+use crate::settings::SettingsStore;
+use crate::theme::{active_theme_name, set_active_theme, theme, Theme, THEME_NAMES};
 use gpui3::{
-    div, img, svg, view, AppContext, ArcCow, Context, Element, IntoAnyElement, ParentElement,
+    div, img, svg, view, AppContext, ArcCow, Context, Element, MouseButton, ParentElement,
     ScrollState, StyleHelpers, View, ViewContext, WindowContext,
 };
+pub use model::{CollabPanelModel, EntryKind, ListEntry, Section};
+
+// The minimum a matched character can contribute to a fuzzy match score; kept low
+// (and non-negative) so that a valid subsequence match is never scored below this
+// floor, no matter how scattered it is through the candidate.
+const MIN_SCORE_PER_MATCHED_CHAR: i32 = 1;
+
+// The largest bonus a matched character can earn for being close to the previous
+// match (or, for the first matched character, close to the start of the
+// candidate). The bonus shrinks by one per skipped character and floors at zero,
+// so gap size is scored on a gradient rather than as an all-or-nothing bonus.
+const GAP_BONUS_CAP: i32 = 3;
+
+// Items scoring below `query.chars().count() * FUZZY_MATCH_THRESHOLD` are treated as
+// non-matches and hidden from the list.
+const FUZZY_MATCH_THRESHOLD: i32 = MIN_SCORE_PER_MATCHED_CHAR;
 
 pub struct CollabPanel {
     scroll_state: ScrollState,
+    model: CollabPanelModel,
+    filter_query: String,
+    selected_item: Option<usize>,
 }
 
 pub fn collab_panel<S: 'static>(cx: &mut WindowContext) -> View<CollabPanel, S> {
@@ -13,10 +35,166 @@ pub fn collab_panel<S: 'static>(cx: &mut WindowContext) -> View<CollabPanel, S>
 }
 
 impl CollabPanel {
-    fn new(_: &mut AppContext) -> Self {
+    fn new(cx: &mut AppContext) -> Self {
+        let mut model = CollabPanelModel::new();
+
+        model.push_section(Section::new("#CRDB 🗃️"));
+        model.push_entry(
+            "#CRDB 🗃️",
+            ListEntry::contact("http://github.com/maxbrunsfeld.png?s=50", "maxbrunsfeld"),
+        );
+
+        model.push_section(Section::new("CHANNELS"));
+
+        model.push_section(Section::new("CONTACTS"));
+        for _ in 0..10 {
+            model.push_entry(
+                "CONTACTS",
+                ListEntry::contact("http://github.com/as-cii.png?s=50", "as-cii"),
+            );
+            model.push_entry(
+                "CONTACTS",
+                ListEntry::contact("http://github.com/nathansobo.png?s=50", "nathansobo"),
+            );
+            model.push_entry(
+                "CONTACTS",
+                ListEntry::contact("http://github.com/maxbrunsfeld.png?s=50", "maxbrunsfeld"),
+            );
+        }
+
+        let persisted_collapsed_sections =
+            cx.global::<SettingsStore>().collab_panel_collapsed_sections();
+        for section in model.sections_mut() {
+            section.collapsed = persisted_collapsed_sections.contains(&*section.title);
+        }
+
+        set_active_theme(cx, &cx.global::<SettingsStore>().active_theme_name());
+
         CollabPanel {
             scroll_state: ScrollState::default(),
+            model,
+            filter_query: String::new(),
+            selected_item: None,
+        }
+    }
+
+    fn toggle_section(&mut self, title: ArcCow<'static, str>, cx: &mut ViewContext<Self>) {
+        let Some(section) = self.model.section_mut(&title) else {
+            return;
+        };
+        section.collapsed = !section.collapsed;
+        let collapsed = section.collapsed;
+
+        cx.global::<SettingsStore>()
+            .set_collab_panel_section_collapsed(&title, collapsed);
+        cx.notify();
+    }
+
+    /// Scores `candidate` against `query` as a case-insensitive subsequence match,
+    /// returning `None` if `query`'s characters don't all appear in order. Every
+    /// matched character contributes at least `MIN_SCORE_PER_MATCHED_CHAR`, so a
+    /// successful match is never hidden by the ranking. On top of that floor, each
+    /// character earns a bonus of up to `GAP_BONUS_CAP` that shrinks by one for
+    /// every character skipped since the previous match (or, for the first matched
+    /// character, since the start of `candidate`) — so tighter, earlier matches
+    /// simply sort higher instead of looser ones being penalized away.
+    fn fuzzy_match_score(query: &str, candidate: &str) -> Option<i32> {
+        if query.is_empty() {
+            return Some(0);
+        }
+
+        let candidate_lower = candidate.to_lowercase();
+        let mut candidate_chars = candidate_lower.char_indices();
+        let mut score = 0;
+        let mut last_match_index: Option<usize> = None;
+
+        for query_char in query.to_lowercase().chars() {
+            let (index, _) =
+                candidate_chars.by_ref().find(|&(_, c)| c == query_char)?;
+
+            let gap = match last_match_index {
+                Some(last_index) => index - last_index - 1,
+                None => index,
+            };
+
+            score += MIN_SCORE_PER_MATCHED_CHAR + (GAP_BONUS_CAP - gap as i32).max(0);
+
+            last_match_index = Some(index);
+        }
+
+        Some(score)
+    }
+
+    /// Filters and ranks `items` against the current filter query, dropping anything
+    /// below `FUZZY_MATCH_THRESHOLD` and ordering the rest from tightest match to
+    /// loosest.
+    fn matching_items(&self, items: &[ListEntry]) -> Vec<ListEntry> {
+        let threshold = self.filter_query.chars().count() as i32 * FUZZY_MATCH_THRESHOLD;
+        let mut scored: Vec<_> = items
+            .iter()
+            .filter_map(|entry| {
+                Self::fuzzy_match_score(&self.filter_query, &entry.label)
+                    .filter(|score| *score >= threshold)
+                    .map(|score| (score, entry.clone()))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored.into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    fn set_filter_query(&mut self, query: String, cx: &mut ViewContext<Self>) {
+        self.filter_query = query;
+        cx.notify();
+    }
+
+    fn select_item(&mut self, index: usize, cx: &mut ViewContext<Self>) {
+        self.selected_item = Some(index);
+        cx.notify();
+    }
+
+    /// Moves the selection by `delta` through the `visible_count` currently visible
+    /// items, clamping at either end instead of wrapping.
+    fn move_selection(&mut self, delta: isize, visible_count: usize, cx: &mut ViewContext<Self>) {
+        self.selected_item = Self::next_selection(self.selected_item, delta, visible_count);
+        cx.notify();
+    }
+
+    /// Pure selection-clamping logic behind [`Self::move_selection`], pulled out so
+    /// it can be tested without a [`ViewContext`]. Resets to `None` once the
+    /// visible list is empty, selects the first item when nothing is selected yet,
+    /// and otherwise clamps `delta` steps from `current` to stay within
+    /// `0..visible_count`.
+    fn next_selection(
+        current: Option<usize>,
+        delta: isize,
+        visible_count: usize,
+    ) -> Option<usize> {
+        if visible_count == 0 {
+            return None;
         }
+
+        let Some(current) = current else {
+            return Some(0);
+        };
+
+        let current = current as isize;
+        let next = (current + delta).clamp(0, visible_count as isize - 1);
+        Some(next as usize)
+    }
+
+    /// Switches to the next named theme in `THEME_NAMES`, persisting the choice so
+    /// it's restored the next time the panel is created.
+    fn cycle_theme(&mut self, cx: &mut ViewContext<Self>) {
+        let current = active_theme_name(cx);
+        let next_index = THEME_NAMES
+            .iter()
+            .position(|&name| name == current)
+            .map_or(0, |index| (index + 1) % THEME_NAMES.len());
+        let next = THEME_NAMES[next_index];
+
+        set_active_theme(cx, next);
+        cx.global::<SettingsStore>().set_active_theme_name(next);
+        cx.notify();
     }
 }
 
@@ -24,6 +202,29 @@ impl CollabPanel {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl Element<State = Self> {
         let theme = theme(cx);
 
+        let mut next_visible_index = 0;
+        let sections: Vec<_> = self
+            .model
+            .sections()
+            .iter()
+            .map(|section| {
+                let visible_items = self.matching_items(&section.items);
+                let show_header = section.items.is_empty() || !visible_items.is_empty();
+                let start_index = next_visible_index;
+                if !section.collapsed {
+                    next_visible_index += visible_items.len();
+                }
+                (
+                    section.title.clone(),
+                    section.collapsed,
+                    show_header,
+                    visible_items,
+                    start_index,
+                )
+            })
+            .collect();
+        let visible_count = next_visible_index;
+
         // Panel
         div()
             .w_64()
@@ -35,6 +236,11 @@ impl CollabPanel {
             .border_color(theme.middle.base.default.border)
             .border()
             .fill(theme.middle.base.default.background)
+            .on_key_down(move |this, event, cx| match event.keystroke.key.as_str() {
+                "down" => this.move_selection(1, visible_count, cx),
+                "up" => this.move_selection(-1, visible_count, cx),
+                _ => {}
+            })
             .child(
                 div()
                     .w_full()
@@ -42,60 +248,32 @@ impl CollabPanel {
                     .flex_col()
                     .overflow_y_scroll(self.scroll_state.clone())
                     // List Container
-                    .child(
-                        div()
-                            .fill(theme.lowest.base.default.background)
-                            .pb_1()
-                            .border_color(theme.lowest.base.default.border)
-                            .border_b()
-                            //:: https://tailwindcss.com/docs/hover-focus-and-other-states#styling-based-on-parent-state
-                            // .group()
-                            // List Section Header
-                            .child(self.list_section_header("#CRDB 🗃️", true, theme))
-                            // List Item Large
-                            .child(self.list_item(
-                                "http://github.com/maxbrunsfeld.png?s=50",
-                                "maxbrunsfeld",
-                                theme,
-                            )),
-                    )
-                    .child(
-                        div()
-                            .py_2()
-                            .flex()
-                            .flex_col()
-                            .child(self.list_section_header("CHANNELS", true, theme)),
-                    )
-                    .child(
-                        div()
-                            .py_2()
-                            .flex()
-                            .flex_col()
-                            .child(self.list_section_header("CONTACTS", true, theme))
-                            .children(
-                                std::iter::repeat_with(|| {
-                                    vec![
-                                        self.list_item(
-                                            "http://github.com/as-cii.png?s=50",
-                                            "as-cii",
-                                            theme,
-                                        ),
-                                        self.list_item(
-                                            "http://github.com/nathansobo.png?s=50",
-                                            "nathansobo",
-                                            theme,
-                                        ),
-                                        self.list_item(
-                                            "http://github.com/maxbrunsfeld.png?s=50",
-                                            "maxbrunsfeld",
-                                            theme,
-                                        ),
-                                    ]
-                                })
-                                .take(10)
-                                .flatten(),
-                            ),
-                    ),
+                    .children(sections.into_iter().filter_map(
+                        |(title, collapsed, show_header, visible_items, start_index)| {
+                            show_header.then(|| {
+                                div()
+                                    .py_2()
+                                    .flex()
+                                    .flex_col()
+                                    .child(self.list_section_header(title, collapsed, &theme, cx))
+                                    .children((!collapsed).then(|| {
+                                        visible_items
+                                            .iter()
+                                            .enumerate()
+                                            .map(|(offset, entry)| {
+                                                let index = start_index + offset;
+                                                self.list_item(
+                                                    entry,
+                                                    index,
+                                                    self.selected_item == Some(index),
+                                                    &theme,
+                                                )
+                                            })
+                                            .collect::<Vec<_>>()
+                                    }))
+                            })
+                        },
+                    )),
             )
             .child(
                 div()
@@ -104,29 +282,69 @@ impl CollabPanel {
                     .border_t()
                     .border_color(theme.middle.variant.default.border)
                     .flex()
+                    .justify_between()
                     .items_center()
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(if self.filter_query.is_empty() {
+                                theme.middle.variant.default.foreground
+                            } else {
+                                theme.middle.base.default.foreground
+                            })
+                            .on_key_down(move |this, event, cx| {
+                                match event.keystroke.key.as_str() {
+                                    "backspace" => {
+                                        let mut query = this.filter_query.clone();
+                                        query.pop();
+                                        this.set_filter_query(query, cx);
+                                    }
+                                    key if key.chars().count() == 1 => {
+                                        let mut query = this.filter_query.clone();
+                                        query.push_str(key);
+                                        this.set_filter_query(query, cx);
+                                    }
+                                    _ => {}
+                                }
+                            })
+                            .child(if self.filter_query.is_empty() {
+                                "Find...".to_string()
+                            } else {
+                                self.filter_query.clone()
+                            }),
+                    )
                     .child(
                         div()
                             .text_sm()
                             .text_color(theme.middle.variant.default.foreground)
-                            .child("Find..."),
+                            .on_click(MouseButton::Left, |this, _, cx| {
+                                this.cycle_theme(cx);
+                            })
+                            .child(format!("Theme: {}", active_theme_name(cx))),
                     ),
             )
     }
 
     fn list_section_header(
         &self,
-        label: impl IntoAnyElement<Self>,
-        expanded: bool,
+        title: ArcCow<'static, str>,
+        collapsed: bool,
         theme: &Theme,
+        cx: &mut ViewContext<Self>,
     ) -> impl Element<State = Self> {
+        let expanded = !collapsed;
+        let click_title = title.clone();
+
         div()
             .h_7()
             .px_2()
             .flex()
             .justify_between()
             .items_center()
-            .child(div().flex().gap_1().text_sm().child(label))
+            .on_click(MouseButton::Left, move |this, _, cx| {
+                this.toggle_section(click_title.clone(), cx);
+            })
+            .child(div().flex().gap_1().text_sm().child(title))
             .child(
                 div().flex().h_full().gap_1().items_center().child(
                     svg()
@@ -144,8 +362,9 @@ impl CollabPanel {
 
     fn list_item(
         &self,
-        avatar_uri: impl Into<ArcCow<'static, str>>,
-        label: impl IntoAnyElement<Self>,
+        entry: &ListEntry,
+        index: usize,
+        selected: bool,
         theme: &Theme,
     ) -> impl Element<State = Self> {
         div()
@@ -153,10 +372,16 @@ impl CollabPanel {
             .px_2()
             .flex()
             .items_center()
-            // .hover()
-            // .fill(theme.lowest.variant.hovered.background)
-            // .active()
-            // .fill(theme.lowest.variant.pressed.background)
+            .when(selected, |this| {
+                this.fill(theme.lowest.accent.default.background)
+            })
+            .hover()
+            .fill(theme.lowest.variant.hovered.background)
+            .active()
+            .fill(theme.lowest.variant.pressed.background)
+            .on_click(MouseButton::Left, move |this, _, cx| {
+                this.select_item(index, cx);
+            })
             .child(
                 div()
                     .flex()
@@ -165,12 +390,111 @@ impl CollabPanel {
                     .text_sm()
                     .child(
                         img()
-                            .uri(avatar_uri)
+                            .uri(entry.avatar_uri.clone())
                             .size_3p5()
                             .rounded_full()
                             .fill(theme.middle.positive.default.foreground),
                     )
-                    .child(label),
+                    .child(entry.label.clone()),
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::CollabPanel;
+
+    #[test]
+    fn matches_single_character_deep_in_the_candidate() {
+        assert!(CollabPanel::fuzzy_match_score("d", "maxbrunsfeld").is_some());
+    }
+
+    #[test]
+    fn matches_first_and_last_initial() {
+        assert!(CollabPanel::fuzzy_match_score("mf", "maxbrunsfeld").is_some());
+    }
+
+    #[test]
+    fn is_case_insensitive() {
+        assert_eq!(
+            CollabPanel::fuzzy_match_score("MF", "maxbrunsfeld"),
+            CollabPanel::fuzzy_match_score("mf", "maxbrunsfeld"),
+        );
+    }
+
+    #[test]
+    fn rejects_out_of_order_characters() {
+        assert_eq!(CollabPanel::fuzzy_match_score("fm", "maxbrunsfeld"), None);
+    }
+
+    #[test]
+    fn rejects_characters_not_present() {
+        assert_eq!(CollabPanel::fuzzy_match_score("z", "maxbrunsfeld"), None);
+    }
+
+    #[test]
+    fn empty_query_matches_everything_with_a_neutral_score() {
+        assert_eq!(CollabPanel::fuzzy_match_score("", "maxbrunsfeld"), Some(0));
+    }
+
+    #[test]
+    fn contiguous_matches_score_higher_than_scattered_ones() {
+        let contiguous = CollabPanel::fuzzy_match_score("max", "maxbrunsfeld").unwrap();
+        let scattered = CollabPanel::fuzzy_match_score("mxl", "maxbrunsfeld").unwrap();
+        assert!(contiguous > scattered);
+    }
+
+    #[test]
+    fn matches_near_the_start_score_higher_than_matches_further_in() {
+        let near_start = CollabPanel::fuzzy_match_score("ma", "maxbrunsfeld").unwrap();
+        let further_in = CollabPanel::fuzzy_match_score("el", "maxbrunsfeld").unwrap();
+        assert!(near_start > further_in);
+    }
+
+    #[test]
+    fn a_match_one_character_in_still_earns_a_start_bonus() {
+        // "b" is one character into "abrunsfeld", not at index 0 — it should still
+        // score higher than a match deep in the candidate, just not the maximum.
+        let one_in = CollabPanel::fuzzy_match_score("b", "abrunsfeld").unwrap();
+        let deep_in = CollabPanel::fuzzy_match_score("d", "abrunsfeld").unwrap();
+        assert!(one_in > deep_in);
+    }
+
+    #[test]
+    fn gap_bonus_shrinks_gradually_with_distance_rather_than_all_or_nothing() {
+        // Three single-character matches at increasing distance from the start of
+        // the candidate should score in strictly decreasing order, not just "at
+        // the start" vs. "everywhere else".
+        let at_start = CollabPanel::fuzzy_match_score("a", "abcrunsfeld").unwrap();
+        let one_away = CollabPanel::fuzzy_match_score("b", "abcrunsfeld").unwrap();
+        let two_away = CollabPanel::fuzzy_match_score("c", "abcrunsfeld").unwrap();
+        assert!(at_start > one_away);
+        assert!(one_away > two_away);
+    }
+
+    #[test]
+    fn next_selection_defaults_to_the_first_item_when_nothing_is_selected() {
+        assert_eq!(CollabPanel::next_selection(None, 1, 5), Some(0));
+    }
+
+    #[test]
+    fn next_selection_clamps_at_the_end_of_the_visible_list() {
+        assert_eq!(CollabPanel::next_selection(Some(4), 1, 5), Some(4));
+    }
+
+    #[test]
+    fn next_selection_clamps_at_the_start_of_the_visible_list() {
+        assert_eq!(CollabPanel::next_selection(Some(0), -1, 5), Some(0));
+    }
+
+    #[test]
+    fn next_selection_steps_by_delta_within_bounds() {
+        assert_eq!(CollabPanel::next_selection(Some(2), 1, 5), Some(3));
+        assert_eq!(CollabPanel::next_selection(Some(2), -1, 5), Some(1));
+    }
+
+    #[test]
+    fn next_selection_resets_to_none_when_nothing_is_visible() {
+        assert_eq!(CollabPanel::next_selection(Some(2), 1, 0), None);
+    }
+}
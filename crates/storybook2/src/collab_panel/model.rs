@@ -0,0 +1,211 @@
+use gpui3::ArcCow;
+
+/// Whether a [`ListEntry`] represents a direct contact or a shared channel.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EntryKind {
+    Contact,
+    Channel,
+}
+
+/// A single row rendered by `CollabPanel::list_item`.
+#[derive(Clone)]
+pub struct ListEntry {
+    pub avatar_uri: ArcCow<'static, str>,
+    pub label: ArcCow<'static, str>,
+    pub kind: EntryKind,
+}
+
+impl ListEntry {
+    pub fn contact(
+        avatar_uri: impl Into<ArcCow<'static, str>>,
+        label: impl Into<ArcCow<'static, str>>,
+    ) -> Self {
+        Self {
+            avatar_uri: avatar_uri.into(),
+            label: label.into(),
+            kind: EntryKind::Contact,
+        }
+    }
+
+    pub fn channel(
+        avatar_uri: impl Into<ArcCow<'static, str>>,
+        label: impl Into<ArcCow<'static, str>>,
+    ) -> Self {
+        Self {
+            avatar_uri: avatar_uri.into(),
+            label: label.into(),
+            kind: EntryKind::Channel,
+        }
+    }
+}
+
+/// A titled, collapsible group of [`ListEntry`]s, e.g. "CONTACTS" or a channel group.
+pub struct Section {
+    pub title: ArcCow<'static, str>,
+    pub collapsed: bool,
+    pub items: Vec<ListEntry>,
+}
+
+impl Section {
+    pub fn new(title: impl Into<ArcCow<'static, str>>) -> Self {
+        Self {
+            title: title.into(),
+            collapsed: false,
+            items: Vec::new(),
+        }
+    }
+}
+
+/// Holds the sections and entries `CollabPanel` renders, independent of how that
+/// data was produced (demo data today, real collaboration state eventually).
+#[derive(Default)]
+pub struct CollabPanelModel {
+    sections: Vec<Section>,
+}
+
+impl CollabPanelModel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+
+    pub fn sections_mut(&mut self) -> &mut [Section] {
+        &mut self.sections
+    }
+
+    pub fn section(&self, title: &str) -> Option<&Section> {
+        self.sections.iter().find(|section| &*section.title == title)
+    }
+
+    pub fn section_mut(&mut self, title: &str) -> Option<&mut Section> {
+        self.sections
+            .iter_mut()
+            .find(|section| &*section.title == title)
+    }
+
+    pub fn push_section(&mut self, section: Section) {
+        self.sections.push(section);
+    }
+
+    pub fn remove_section(&mut self, title: &str) -> Option<Section> {
+        let index = self
+            .sections
+            .iter()
+            .position(|section| &*section.title == title)?;
+        Some(self.sections.remove(index))
+    }
+
+    pub fn push_entry(&mut self, section_title: &str, entry: ListEntry) {
+        if let Some(section) = self.section_mut(section_title) {
+            section.items.push(entry);
+        }
+    }
+
+    pub fn remove_entry(&mut self, section_title: &str, label: &str) -> Option<ListEntry> {
+        let section = self.section_mut(section_title)?;
+        let index = section
+            .items
+            .iter()
+            .position(|item| &*item.label == label)?;
+        Some(section.items.remove(index))
+    }
+
+    pub fn update_entry(
+        &mut self,
+        section_title: &str,
+        label: &str,
+        update: impl FnOnce(&mut ListEntry),
+    ) {
+        if let Some(section) = self.section_mut(section_title) {
+            if let Some(entry) = section
+                .items
+                .iter_mut()
+                .find(|item| &*item.label == label)
+            {
+                update(entry);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model_with_contacts() -> CollabPanelModel {
+        let mut model = CollabPanelModel::new();
+        model.push_section(Section::new("CONTACTS"));
+        model.push_entry("CONTACTS", ListEntry::contact("avatar/a", "as-cii"));
+        model.push_entry("CONTACTS", ListEntry::contact("avatar/b", "nathansobo"));
+        model
+    }
+
+    #[test]
+    fn push_and_find_section() {
+        let model = model_with_contacts();
+        let section = model.section("CONTACTS").unwrap();
+        assert_eq!(&*section.title, "CONTACTS");
+        assert_eq!(section.items.len(), 2);
+    }
+
+    #[test]
+    fn missing_section_is_none() {
+        let model = model_with_contacts();
+        assert!(model.section("CHANNELS").is_none());
+    }
+
+    #[test]
+    fn remove_section_returns_it_and_drops_it_from_the_model() {
+        let mut model = model_with_contacts();
+        let removed = model.remove_section("CONTACTS").unwrap();
+        assert_eq!(&*removed.title, "CONTACTS");
+        assert!(model.section("CONTACTS").is_none());
+    }
+
+    #[test]
+    fn push_entry_appends_to_the_named_section() {
+        let mut model = model_with_contacts();
+        model.push_entry("CONTACTS", ListEntry::contact("avatar/c", "maxbrunsfeld"));
+        assert_eq!(model.section("CONTACTS").unwrap().items.len(), 3);
+    }
+
+    #[test]
+    fn push_entry_into_missing_section_is_a_noop() {
+        let mut model = model_with_contacts();
+        model.push_entry("CHANNELS", ListEntry::channel("avatar/d", "#crdb"));
+        assert!(model.section("CHANNELS").is_none());
+    }
+
+    #[test]
+    fn remove_entry_returns_the_matching_entry() {
+        let mut model = model_with_contacts();
+        let removed = model.remove_entry("CONTACTS", "as-cii").unwrap();
+        assert_eq!(&*removed.label, "as-cii");
+        assert_eq!(model.section("CONTACTS").unwrap().items.len(), 1);
+    }
+
+    #[test]
+    fn remove_missing_entry_is_none() {
+        let mut model = model_with_contacts();
+        assert!(model.remove_entry("CONTACTS", "ghost").is_none());
+    }
+
+    #[test]
+    fn update_entry_mutates_the_matching_entry_in_place() {
+        let mut model = model_with_contacts();
+        model.update_entry("CONTACTS", "as-cii", |entry| {
+            entry.avatar_uri = "avatar/updated".into();
+        });
+        let entry = model
+            .section("CONTACTS")
+            .unwrap()
+            .items
+            .iter()
+            .find(|entry| &*entry.label == "as-cii")
+            .unwrap();
+        assert_eq!(&*entry.avatar_uri, "avatar/updated");
+    }
+}
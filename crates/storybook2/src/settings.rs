@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+/// The collab panel's persisted display settings: which sections are collapsed
+/// and which theme is active. Stored as a small JSON file under the user's
+/// config directory and loaded lazily the first time it's needed.
+#[derive(Default, Serialize, Deserialize)]
+struct CollabPanelSettings {
+    #[serde(default)]
+    collapsed_sections: HashSet<String>,
+    #[serde(default)]
+    active_theme_name: Option<String>,
+}
+
+pub struct SettingsStore {
+    path: PathBuf,
+    collab_panel: RefCell<CollabPanelSettings>,
+}
+
+impl Default for SettingsStore {
+    fn default() -> Self {
+        Self::load(Self::default_path())
+    }
+}
+
+impl SettingsStore {
+    fn default_path() -> PathBuf {
+        let mut path = std::env::var_os("HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("."));
+        path.push(".config/zed/collab_panel_settings.json");
+        path
+    }
+
+    fn load(path: PathBuf) -> Self {
+        let collab_panel = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        Self {
+            path,
+            collab_panel: RefCell::new(collab_panel),
+        }
+    }
+
+    fn persist(&self) {
+        if let Some(parent) = self.path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(contents) = serde_json::to_string_pretty(&*self.collab_panel.borrow()) {
+            let _ = fs::write(&self.path, contents);
+        }
+    }
+
+    pub fn collab_panel_collapsed_sections(&self) -> HashSet<String> {
+        self.collab_panel.borrow().collapsed_sections.clone()
+    }
+
+    pub fn set_collab_panel_section_collapsed(&self, label: &str, collapsed: bool) {
+        {
+            let mut settings = self.collab_panel.borrow_mut();
+            if collapsed {
+                settings.collapsed_sections.insert(label.to_string());
+            } else {
+                settings.collapsed_sections.remove(label);
+            }
+        }
+        self.persist();
+    }
+
+    pub fn active_theme_name(&self) -> String {
+        self.collab_panel
+            .borrow()
+            .active_theme_name
+            .clone()
+            .unwrap_or_else(|| "dark".to_string())
+    }
+
+    pub fn set_active_theme_name(&self, name: &str) {
+        self.collab_panel.borrow_mut().active_theme_name = Some(name.to_string());
+        self.persist();
+    }
+}
@@ -0,0 +1,155 @@
+use gpui3::{rgb, Context, Hsla};
+use std::cell::RefCell;
+
+/// Names of the themes the collab panel's theme picker cycles through.
+pub const THEME_NAMES: &[&str] = &["light", "dark", "ayu"];
+
+#[derive(Clone, Copy)]
+pub struct ColorSet {
+    pub foreground: Hsla,
+    pub background: Hsla,
+    pub border: Hsla,
+}
+
+#[derive(Clone, Copy)]
+pub struct StateColors {
+    pub default: ColorSet,
+    pub hovered: ColorSet,
+    pub pressed: ColorSet,
+}
+
+#[derive(Clone, Copy)]
+pub struct ColorRamp {
+    pub base: StateColors,
+    pub variant: StateColors,
+    pub positive: StateColors,
+    pub accent: StateColors,
+}
+
+#[derive(Clone, Copy)]
+pub struct Theme {
+    pub name: &'static str,
+    pub lowest: ColorRamp,
+    pub middle: ColorRamp,
+    pub highest: ColorRamp,
+}
+
+fn color_set(background: u32, foreground: u32, border: u32) -> ColorSet {
+    ColorSet {
+        background: rgb(background).into(),
+        foreground: rgb(foreground).into(),
+        border: rgb(border).into(),
+    }
+}
+
+fn state_colors(
+    default_bg: u32,
+    hovered_bg: u32,
+    pressed_bg: u32,
+    foreground: u32,
+    border: u32,
+) -> StateColors {
+    StateColors {
+        default: color_set(default_bg, foreground, border),
+        hovered: color_set(hovered_bg, foreground, border),
+        pressed: color_set(pressed_bg, foreground, border),
+    }
+}
+
+fn ramp(
+    default_bg: u32,
+    hovered_bg: u32,
+    pressed_bg: u32,
+    foreground: u32,
+    border: u32,
+    accent_bg: u32,
+) -> ColorRamp {
+    ColorRamp {
+        base: state_colors(default_bg, hovered_bg, pressed_bg, foreground, border),
+        variant: state_colors(default_bg, hovered_bg, pressed_bg, foreground, border),
+        positive: state_colors(default_bg, hovered_bg, pressed_bg, 0x3fb950, border),
+        accent: state_colors(accent_bg, accent_bg, accent_bg, foreground, border),
+    }
+}
+
+fn light_theme() -> Theme {
+    Theme {
+        name: "light",
+        lowest: ramp(0xffffff, 0xf0f0f2, 0xe2e2e6, 0x1e1e24, 0xd8d8dc, 0x2f6fed),
+        middle: ramp(0xf7f7f9, 0xeeeef1, 0xe2e2e6, 0x1e1e24, 0xd8d8dc, 0x2f6fed),
+        highest: ramp(0xeeeef1, 0xe6e6ea, 0xdadadf, 0x1e1e24, 0xd0d0d5, 0x2f6fed),
+    }
+}
+
+fn dark_theme() -> Theme {
+    Theme {
+        name: "dark",
+        lowest: ramp(0x1e1e22, 0x26262c, 0x303038, 0xe6e6e6, 0x38383f, 0x5865f2),
+        middle: ramp(0x26262c, 0x2e2e35, 0x38383f, 0xe6e6e6, 0x3a3a42, 0x5865f2),
+        highest: ramp(0x2e2e35, 0x38383f, 0x44444c, 0xffffff, 0x44444c, 0x5865f2),
+    }
+}
+
+/// A high-contrast, warm-accented variant analogous to the Ayu family.
+fn ayu_theme() -> Theme {
+    Theme {
+        name: "ayu",
+        lowest: ramp(0x0f1419, 0x1b232a, 0x273138, 0xe6e1cf, 0x273138, 0xff8f40),
+        middle: ramp(0x1b232a, 0x242e35, 0x303a42, 0xe6e1cf, 0x303a42, 0xff8f40),
+        highest: ramp(0x242e35, 0x303a42, 0x3c464e, 0xffffff, 0x3c464e, 0xff8f40),
+    }
+}
+
+fn theme_by_name(name: &str) -> Theme {
+    match name {
+        "light" => light_theme(),
+        "ayu" => ayu_theme(),
+        _ => dark_theme(),
+    }
+}
+
+fn canonical_name(name: &str) -> &'static str {
+    THEME_NAMES
+        .iter()
+        .copied()
+        .find(|candidate| *candidate == name)
+        .unwrap_or("dark")
+}
+
+pub struct ThemeRegistry {
+    active_name: RefCell<&'static str>,
+}
+
+impl Default for ThemeRegistry {
+    fn default() -> Self {
+        Self {
+            active_name: RefCell::new("dark"),
+        }
+    }
+}
+
+impl ThemeRegistry {
+    fn active(&self) -> Theme {
+        theme_by_name(&self.active_name.borrow())
+    }
+
+    fn active_name(&self) -> &'static str {
+        *self.active_name.borrow()
+    }
+
+    fn set_active(&self, name: &str) {
+        *self.active_name.borrow_mut() = canonical_name(name);
+    }
+}
+
+pub fn theme<C: Context>(cx: &mut C) -> Theme {
+    cx.global::<ThemeRegistry>().active()
+}
+
+pub fn active_theme_name<C: Context>(cx: &mut C) -> &'static str {
+    cx.global::<ThemeRegistry>().active_name()
+}
+
+pub fn set_active_theme<C: Context>(cx: &mut C, name: &str) {
+    cx.global::<ThemeRegistry>().set_active(name);
+}